@@ -0,0 +1,297 @@
+//! Provides structures and functions used to capture audio inputs.
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+};
+
+use log::error;
+use rodio::cpal::{
+    self,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    BuildStreamError, DefaultStreamConfigError, PlayStreamError, SampleFormat, Stream,
+    StreamConfig,
+};
+use rodio::Device;
+
+use crate::source::Source;
+
+/// Number of sample frames buffered before the capture callback starts dropping them.
+const CAPTURE_BUFFER_FRAMES: usize = 48_000;
+
+/// A pollable handle to an audio capture, drained off the game thread.
+///
+/// Obtained from [`init_input`] alongside the [`Stream`] that keeps the device alive. The capture
+/// callback only pushes into a bounded channel, so a slow consumer drops frames (counted by
+/// [`Input::dropped_frames`]) rather than blocking the backend. Unlike the [`Stream`], this handle
+/// is [`Send`], so captured audio can be drained from a different thread than the one that called
+/// [`init_input`].
+pub struct Input {
+    /// Name of the input device being used.
+    pub name: String,
+    /// The sample format negotiated with the device.
+    config: StreamConfig,
+    /// Receiving end of the capture channel, drained on the game thread.
+    receiver: Receiver<f32>,
+    /// Number of samples the callback had to drop, counted per channel rather than per frame.
+    dropped: Arc<AtomicU64>,
+}
+
+impl Input {
+    /// The sample rate, in Hz, of the captured audio.
+    #[must_use]
+    pub fn sample_rate(&self) -> u32 {
+        self.config.sample_rate.0
+    }
+
+    /// The number of interleaved channels in the captured audio.
+    #[must_use]
+    pub fn channels(&self) -> u16 {
+        self.config.channels
+    }
+
+    /// Drains the frames captured since the previous call as `f32` samples in `-1.0..=1.0`.
+    pub fn frames_f32(&self) -> impl Iterator<Item = f32> + '_ {
+        self.receiver.try_iter()
+    }
+
+    /// Drains the frames captured since the previous call as `i16` samples.
+    pub fn frames_i16(&self) -> impl Iterator<Item = i16> + '_ {
+        self.frames_f32().map(f32_to_i16)
+    }
+
+    /// The number of samples dropped because the game thread could not keep up.
+    ///
+    /// Each channel's sample counts separately, so a dropped stereo frame adds 2.
+    #[must_use]
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Drains the samples captured since the previous drain into a WAV-encoded [`Source`] ready
+    /// for playback. Samples already taken by [`Input::frames_f32`] or [`Input::frames_i16`] are
+    /// not included.
+    #[must_use]
+    pub fn into_source(&self) -> Source {
+        let samples: Vec<i16> = self.frames_i16().collect();
+        Source {
+            bytes: encode_wav(&samples, self.channels(), self.sample_rate()),
+        }
+    }
+}
+
+impl Debug for Input {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Input")
+            .field("device", &self.name)
+            .field("dropped_frames", &self.dropped_frames())
+            .finish()
+    }
+}
+
+/// Audio capture error.
+#[derive(Debug)]
+pub enum InputError {
+    /// No input device was available on the system.
+    NoDevice,
+    /// Querying the device's default input configuration failed.
+    DefaultStreamConfigError(DefaultStreamConfigError),
+    /// Building the capture stream on the device failed.
+    BuildStreamError(BuildStreamError),
+    /// Starting the capture stream failed.
+    PlayStreamError(PlayStreamError),
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+        formatter.write_str("InputError")
+    }
+}
+
+/// Initializes ([`Stream`], [`Input`]) from the default input device, starting the capture.
+///
+/// Mirrors [`init_output`](crate::output::init_output): the [`Stream`] must be kept alive for
+/// capture to continue and stays on the calling thread, while the [`Input`] handle can be moved to
+/// whichever thread drains the samples.
+///
+/// # Errors
+///
+/// Returns [`InputError::NoDevice`] if the system exposes no input device, or another
+/// [`InputError`] variant if the device cannot be configured or its stream cannot be started.
+pub fn init_input() -> Result<(Stream, Input), InputError> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or(InputError::NoDevice)?;
+
+    init_input_from_device(&device)
+}
+
+/// Initializes ([`Stream`], [`Input`]) from the specified input device, starting the capture.
+///
+/// # Errors
+///
+/// Returns an [`InputError`] variant if the device cannot be configured or its stream cannot be
+/// started.
+pub fn init_input_from_device(device: &Device) -> Result<(Stream, Input), InputError> {
+    let name = device
+        .name()
+        .unwrap_or_else(|_| String::from("Unknown device"));
+
+    let supported = device
+        .default_input_config()
+        .map_err(InputError::DefaultStreamConfigError)?;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+
+    let (sender, receiver) = sync_channel::<f32>(CAPTURE_BUFFER_FRAMES * config.channels as usize);
+    let dropped = Arc::new(AtomicU64::new(0));
+
+    let stream = build_input_stream(device, &config, sample_format, sender, Arc::clone(&dropped))
+        .map_err(InputError::BuildStreamError)?;
+    stream.play().map_err(InputError::PlayStreamError)?;
+
+    let input = Input {
+        name,
+        config,
+        receiver,
+        dropped,
+    };
+
+    Ok((stream, input))
+}
+
+/// Builds a capture stream whose callback converts samples to `f32`, pushes them onto the channel,
+/// and counts any it cannot enqueue as dropped.
+fn build_input_stream(
+    device: &Device,
+    config: &StreamConfig,
+    sample_format: SampleFormat,
+    sender: SyncSender<f32>,
+    dropped: Arc<AtomicU64>,
+) -> Result<Stream, BuildStreamError> {
+    let err_fn = |err| error!("An error occurred on the input stream: {:?}", err);
+
+    match sample_format {
+        SampleFormat::F32 => device.build_input_stream(
+            config,
+            move |data: &[f32], _| push_samples(data.iter().copied(), &sender, &dropped),
+            err_fn,
+            None,
+        ),
+        SampleFormat::I16 => device.build_input_stream(
+            config,
+            move |data: &[i16], _| push_samples(data.iter().map(|&s| i16_to_f32(s)), &sender, &dropped),
+            err_fn,
+            None,
+        ),
+        SampleFormat::U16 => device.build_input_stream(
+            config,
+            move |data: &[u16], _| push_samples(data.iter().map(|&s| u16_to_f32(s)), &sender, &dropped),
+            err_fn,
+            None,
+        ),
+        other => {
+            error!("Unsupported capture sample format: {:?}", other);
+            Err(BuildStreamError::StreamConfigNotSupported)
+        }
+    }
+}
+
+/// Pushes samples onto the capture channel, incrementing `dropped` for any that do not fit.
+fn push_samples(samples: impl Iterator<Item = f32>, sender: &SyncSender<f32>, dropped: &AtomicU64) {
+    let mut lost = 0;
+    for sample in samples {
+        match sender.try_send(sample) {
+            Ok(()) => {}
+            // Either the buffer is full or the consumer is gone; account for the loss either way.
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => lost += 1,
+        }
+    }
+    if lost != 0 {
+        dropped.fetch_add(lost, Ordering::Relaxed);
+    }
+}
+
+/// Encodes interleaved `i16` samples into a canonical 16-bit PCM WAV byte stream.
+fn encode_wav(samples: &[i16], channels: u16, sample_rate: u32) -> Vec<u8> {
+    let bits_per_sample: u16 = 16;
+    let block_align = channels * (bits_per_sample / 8);
+    let byte_rate = sample_rate * u32::from(block_align);
+    let data_len = (samples.len() * 2) as u32;
+
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&byte_rate.to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&bits_per_sample.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+/// Converts a native `f32` sample into the `i16` range with saturation.
+fn f32_to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+}
+
+/// Converts an `i16` sample into the `-1.0..=1.0` `f32` range.
+fn i16_to_f32(sample: i16) -> f32 {
+    (f32::from(sample) / f32::from(i16::MAX)).max(-1.0)
+}
+
+/// Converts an unsigned `u16` sample into the `-1.0..=1.0` `f32` range.
+fn u16_to_f32(sample: u16) -> f32 {
+    (f32::from(sample) / f32::from(u16::MAX)) * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_wav;
+
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes(bytes[offset..offset + 2].try_into().unwrap())
+    }
+
+    #[test]
+    fn encode_wav_header() {
+        // Two stereo frames at 44.1 kHz => four i16 samples, eight bytes of PCM data.
+        let samples = [1i16, -1, 2, -2];
+        let wav = encode_wav(&samples, 2, 44_100);
+
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(read_u32(&wav, 4), 36 + 8); // chunk size = 36 + data
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(&wav[12..16], b"fmt ");
+        assert_eq!(read_u32(&wav, 16), 16); // PCM fmt chunk length
+        assert_eq!(read_u16(&wav, 20), 1); // PCM format tag
+        assert_eq!(read_u16(&wav, 22), 2); // channels
+        assert_eq!(read_u32(&wav, 24), 44_100); // sample rate
+        assert_eq!(read_u32(&wav, 28), 44_100 * 4); // byte rate = rate * block_align
+        assert_eq!(read_u16(&wav, 32), 4); // block_align = channels * 2
+        assert_eq!(read_u16(&wav, 34), 16); // bits per sample
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(read_u32(&wav, 40), 8); // data length
+        assert_eq!(wav.len(), 44 + 8);
+    }
+}
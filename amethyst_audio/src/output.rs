@@ -7,6 +7,7 @@ use std::{
 };
 
 use log::error;
+use rodio::cpal::{self, traits::HostTrait, DeviceNameError, DevicesError, HostId};
 use rodio::OutputStream;
 use rodio::{Device, DeviceTrait, OutputStreamHandle, PlayError, StreamError};
 
@@ -93,6 +94,15 @@ pub enum OutputError {
     DecoderError(DecoderError),
     /// Rodio's error, might mean that rodio has failed to decode a fail or a device is lost.
     PlayError(PlayError),
+    /// Initializing an [`OutputStream`] from a device failed, usually because the device was
+    /// unplugged between enumeration and selection.
+    StreamError(StreamError),
+    /// Enumerating the output devices of a [`Host`] failed.
+    DevicesError(DevicesError),
+    /// Querying a device's name failed, so it could not be matched against a requested name.
+    DeviceNameError(DeviceNameError),
+    /// No device on the host matched the requested name, e.g. because it was unplugged.
+    DeviceNotFound(String),
 }
 
 impl Error for OutputError {}
@@ -103,37 +113,83 @@ impl Display for OutputError {
     }
 }
 
-/*
-/// An iterator over outputs
-#[allow(missing_debug_implementations)]
-pub struct OutputIterator {
-    devices: OutputDevices<Devices>,
+/// A handle to one of the system's audio backends, such as ALSA, WASAPI or CoreAudio.
+pub struct Host {
+    id: HostId,
+    host: cpal::Host,
 }
 
-impl Iterator for OutputIterator {
-    type Item = Output;
+impl Host {
+    /// The human-readable name of the backend, e.g. `"ALSA"` or `"WASAPI"`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.id.name()
+    }
 
-    fn next(&mut self) -> Option<Output> {
-        self.devices.next().map(|device| Output {
-            device: Arc::new(device),
-        })
+    /// Enumerates the output devices exposed by this backend, pairing each device with its name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutputError::DevicesError`] if the backend refuses to enumerate its devices, or
+    /// [`OutputError::DeviceNameError`] if a device's name cannot be queried.
+    pub fn output_devices(&self) -> Result<Vec<(String, Device)>, OutputError> {
+        let devices = self
+            .host
+            .output_devices()
+            .map_err(OutputError::DevicesError)?;
+
+        devices
+            .map(|device| {
+                let name = device.name().map_err(OutputError::DeviceNameError)?;
+                Ok((name, device))
+            })
+            .collect()
     }
 }
 
-/// Get a list of outputs available to the system.
-///
-/// # Panics
+impl Debug for Host {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Host").field("name", &self.name()).finish()
+    }
+}
+
+/// Get a list of the audio backends available on the system.
 ///
-/// Panics if the system does not support audio output and hence no output devices
-/// are found.
+/// Backends that are compiled in but fail to initialize are skipped.
 #[must_use]
-pub fn outputs() -> OutputIterator {
-    let devices = cpal::default_host()
-        .output_devices()
-        .unwrap_or_else(|e| panic!("Error retrieving output devices: `{}`", e));
-    OutputIterator { devices }
+pub fn hosts() -> Vec<Host> {
+    cpal::available_hosts()
+        .into_iter()
+        .filter_map(|id| {
+            cpal::host_from_id(id)
+                .ok()
+                .map(|host| Host { id, host })
+        })
+        .collect()
+}
+
+/// Initializes ([`OutputStream`], [`Output`]) from the named device on the given [`Host`].
+///
+/// The name is one reported by [`Host::output_devices`]; if it no longer matches any device, the
+/// caller can re-enumerate and fall back to [`init_output`].
+///
+/// # Errors
+///
+/// Returns [`OutputError::DevicesError`]/[`OutputError::DeviceNameError`] if the host cannot be
+/// enumerated, [`OutputError::DeviceNotFound`] if no device on the host matches `name`, and
+/// [`OutputError::StreamError`] if opening the matched device fails.
+pub fn init_output_from_host_device(
+    host: &Host,
+    name: &str,
+) -> Result<(OutputStream, Output), OutputError> {
+    let (_, device) = host
+        .output_devices()?
+        .into_iter()
+        .find(|(device_name, _)| device_name == name)
+        .ok_or_else(|| OutputError::DeviceNotFound(name.to_string()))?;
+
+    init_output_from_device(&device).map_err(OutputError::StreamError)
 }
-*/
 
 /// Initializes ([`OutputStream`], [`Output`]) from the default output device.
 ///
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::event::InputEvent;
 
 /// Controller axes matching SDL controller model
-#[derive(Eq, PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
 pub enum ControllerAxis {
     /// The X axis on the left stick
     LeftX,
@@ -139,3 +141,212 @@ impl From<&ControllerEvent> for InputEvent {
         }
     }
 }
+
+/// The full-scale magnitude of a raw SDL axis value, used to normalize into `-1.0..=1.0`.
+const AXIS_FULL_SCALE: f32 = 32767.0;
+
+/// Per-axis dead-zone parameters, expressed as fractions of full deflection.
+///
+/// Raw magnitudes at or below `d_in` are clamped to rest, magnitudes at or above `d_out` saturate
+/// to full deflection, and the range in between is rescaled linearly so the response is continuous
+/// at both boundaries and never jumps.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct DeadZone {
+    /// Inner dead-zone: input magnitude below this (0.0..=1.0) reads as rest.
+    pub d_in: f32,
+    /// Outer saturation: input magnitude above this (0.0..=1.0) reads as full deflection.
+    pub d_out: f32,
+}
+
+impl Default for DeadZone {
+    fn default() -> Self {
+        DeadZone {
+            d_in: 0.15,
+            d_out: 0.95,
+        }
+    }
+}
+
+impl DeadZone {
+    /// Maps a signed raw magnitude `m` (already normalized to `-1.0..=1.0`) through this dead-zone,
+    /// preserving sign and producing a result in `-1.0..=1.0`.
+    fn apply(self, m: f32) -> f32 {
+        m.signum() * self.scale(m.abs())
+    }
+
+    /// Maps a non-negative magnitude through the dead-zone curve, returning a value in `0.0..=1.0`.
+    fn scale(self, magnitude: f32) -> f32 {
+        if magnitude <= self.d_in {
+            0.0
+        } else if magnitude >= self.d_out {
+            1.0
+        } else {
+            (magnitude - self.d_in) / (self.d_out - self.d_in)
+        }
+    }
+}
+
+/// Normalization stage that rescales raw axis motion into clean `-1.0..=1.0` [`InputEvent`]s with a
+/// dead-zone applied at ingestion, radially for paired stick axes and scalar for triggers.
+#[derive(Debug, Default)]
+pub struct AxisCalibration {
+    /// Dead-zone overrides keyed by `(which, axis)`; absent entries use [`DeadZone::default`].
+    dead_zones: HashMap<(u32, ControllerAxis), DeadZone>,
+    /// Latest raw normalized magnitude of each axis, needed to pair stick axes radially.
+    raw: HashMap<(u32, ControllerAxis), f32>,
+}
+
+impl AxisCalibration {
+    /// Creates a calibration stage with default dead-zones for every axis.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the dead-zone for a specific axis of a specific controller.
+    pub fn set_dead_zone(&mut self, which: u32, axis: ControllerAxis, dead_zone: DeadZone) {
+        self.dead_zones.insert((which, axis), dead_zone);
+    }
+
+    /// Returns the dead-zone configured for `(which, axis)`, or the default if none was set.
+    #[must_use]
+    pub fn dead_zone(&self, which: u32, axis: ControllerAxis) -> DeadZone {
+        self.dead_zones
+            .get(&(which, axis))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Converts a controller event into input events, applying the dead-zone to axis motion.
+    ///
+    /// A paired stick axis yields both axes of its stick so the reported 2D vector stays
+    /// consistent (and inside the unit circle); every other event yields a single input event.
+    pub fn ingest(&mut self, event: &ControllerEvent) -> Vec<InputEvent> {
+        if let ControllerEvent::ControllerAxisMoved { which, axis, value } = *event {
+            let raw = (value / AXIS_FULL_SCALE).clamp(-1.0, 1.0);
+            self.raw.insert((which, axis), raw);
+            match partner_axis(axis) {
+                Some(partner) => vec![
+                    InputEvent::ControllerAxisMoved {
+                        which,
+                        axis,
+                        value: self.radial(which, axis, partner),
+                    },
+                    InputEvent::ControllerAxisMoved {
+                        which,
+                        axis: partner,
+                        value: self.radial(which, partner, axis),
+                    },
+                ],
+                None => vec![InputEvent::ControllerAxisMoved {
+                    which,
+                    axis,
+                    value: self.dead_zone(which, axis).apply(raw),
+                }],
+            }
+        } else {
+            vec![event.into()]
+        }
+    }
+
+    /// Computes the dead-zoned component of `axis` from the 2D stick vector formed with `partner`.
+    fn radial(&self, which: u32, axis: ControllerAxis, partner: ControllerAxis) -> f32 {
+        let this = self.raw.get(&(which, axis)).copied().unwrap_or(0.0);
+        let other = self.raw.get(&(which, partner)).copied().unwrap_or(0.0);
+        let magnitude = (this * this + other * other).sqrt();
+        if magnitude < f32::EPSILON {
+            return 0.0;
+        }
+        let scaled = self.dead_zone(which, axis).scale(magnitude.min(1.0));
+        (this / magnitude) * scaled
+    }
+}
+
+/// Returns the axis paired with `axis` on the same analog stick, or `None` for the triggers which
+/// are dead-zoned individually.
+fn partner_axis(axis: ControllerAxis) -> Option<ControllerAxis> {
+    Some(match axis {
+        ControllerAxis::LeftX => ControllerAxis::LeftY,
+        ControllerAxis::LeftY => ControllerAxis::LeftX,
+        ControllerAxis::RightX => ControllerAxis::RightY,
+        ControllerAxis::RightY => ControllerAxis::RightX,
+        ControllerAxis::LeftTrigger | ControllerAxis::RightTrigger => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AxisCalibration, ControllerAxis, ControllerEvent, DeadZone};
+    use crate::event::InputEvent;
+
+    const EPS: f32 = 1e-5;
+
+    #[test]
+    fn scale_rest_saturation_and_linear_segment() {
+        let dz = DeadZone {
+            d_in: 0.2,
+            d_out: 0.8,
+        };
+        // Rest below the inner dead-zone, saturation above the outer.
+        assert_eq!(dz.scale(0.1), 0.0);
+        assert_eq!(dz.scale(0.9), 1.0);
+        // Continuous at both boundaries.
+        assert!(dz.scale(0.2).abs() < EPS);
+        assert!((dz.scale(0.8) - 1.0).abs() < EPS);
+        // Linear halfway through the live range.
+        assert!((dz.scale(0.5) - 0.5).abs() < EPS);
+    }
+
+    #[test]
+    fn apply_preserves_sign() {
+        let dz = DeadZone {
+            d_in: 0.0,
+            d_out: 1.0,
+        };
+        assert!((dz.apply(0.5) - 0.5).abs() < EPS);
+        assert!((dz.apply(-0.5) + 0.5).abs() < EPS);
+    }
+
+    #[test]
+    fn radial_pair_stays_within_unit_circle() {
+        let mut calibration = AxisCalibration::new();
+        let no_dead_zone = DeadZone {
+            d_in: 0.0,
+            d_out: 1.0,
+        };
+        calibration.set_dead_zone(0, ControllerAxis::LeftX, no_dead_zone);
+        calibration.set_dead_zone(0, ControllerAxis::LeftY, no_dead_zone);
+
+        // Hold LeftY fully deflected, then push LeftX fully.
+        calibration.ingest(&ControllerEvent::ControllerAxisMoved {
+            which: 0,
+            axis: ControllerAxis::LeftY,
+            value: 32767.0,
+        });
+        let events = calibration.ingest(&ControllerEvent::ControllerAxisMoved {
+            which: 0,
+            axis: ControllerAxis::LeftX,
+            value: 32767.0,
+        });
+
+        // Both stick axes are re-emitted so the reported vector is consistent.
+        let mut x = None;
+        let mut y = None;
+        for event in events {
+            if let InputEvent::ControllerAxisMoved { axis, value, .. } = event {
+                match axis {
+                    ControllerAxis::LeftX => x = Some(value),
+                    ControllerAxis::LeftY => y = Some(value),
+                    _ => unreachable!(),
+                }
+            }
+        }
+        let x = x.expect("LeftX emitted");
+        let y = y.expect("LeftY emitted");
+        let expected = 1.0 / 2.0_f32.sqrt();
+        assert!((x - expected).abs() < EPS);
+        assert!((y - expected).abs() < EPS);
+        // The resulting vector magnitude is clamped to the unit circle.
+        assert!(((x * x + y * y).sqrt() - 1.0).abs() < EPS);
+    }
+}
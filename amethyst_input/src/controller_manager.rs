@@ -0,0 +1,422 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io,
+    path::Path,
+};
+
+use log::warn;
+
+use crate::controller::{ControllerAxis, ControllerButton, ControllerEvent};
+
+/// Tracks the currently-connected controllers and their latest button/axis state.
+///
+/// Feed canonical [`ControllerEvent`]s through [`process`](Self::process), or remap raw joystick
+/// inputs through an SDL [`MappingDb`] with [`connect`](Self::connect) and the `process_raw_*`
+/// methods, then query with [`is_pressed`](Self::is_pressed) and [`axis_value`](Self::axis_value).
+#[derive(Debug, Default)]
+pub struct ControllerManager {
+    /// Latest state of each live controller, keyed by instance id.
+    controllers: HashMap<u32, ControllerState>,
+    /// GUID-keyed mapping database used to remap unknown pads.
+    mappings: MappingDb,
+}
+
+/// The latest known state of a single controller.
+#[derive(Debug, Default)]
+struct ControllerState {
+    /// Buttons currently held down.
+    buttons: HashSet<ControllerButton>,
+    /// Last reported value of each axis, in the raw SDL `-32768..=32767` range.
+    axes: HashMap<ControllerAxis, f32>,
+    /// Index-keyed bindings resolved from this controller's mapping, used to remap the raw
+    /// joystick inputs fed through the `process_raw_*` methods. Empty for controllers connected
+    /// without a GUID, whose canonical events are taken at face value.
+    mapping: ResolvedMapping,
+}
+
+impl ControllerManager {
+    /// Creates an empty manager with no controllers and no mappings loaded.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the live-controller list and state from a single canonical controller event,
+    /// registering the addressed controller on first sight.
+    pub fn process(&mut self, event: &ControllerEvent) {
+        match *event {
+            ControllerEvent::ControllerConnected { which } => {
+                self.controllers.entry(which).or_default();
+            }
+            ControllerEvent::ControllerDisconnected { which } => {
+                self.controllers.remove(&which);
+            }
+            ControllerEvent::ControllerButtonPressed { which, button } => {
+                self.controllers.entry(which).or_default().buttons.insert(button);
+            }
+            ControllerEvent::ControllerButtonReleased { which, button } => {
+                self.controllers
+                    .entry(which)
+                    .or_default()
+                    .buttons
+                    .remove(&button);
+            }
+            ControllerEvent::ControllerAxisMoved { which, axis, value } => {
+                self.controllers.entry(which).or_default().axes.insert(axis, value);
+            }
+        }
+    }
+
+    /// Registers a controller by its SDL `guid`, resolving the mapping its raw inputs are remapped
+    /// through; GUIDs absent from the database fall back to the registered default mapping.
+    pub fn connect(&mut self, which: u32, guid: &str) {
+        let mapping = self
+            .mappings
+            .get(guid)
+            .map_or_else(ResolvedMapping::default, ControllerMapping::resolve);
+        self.controllers.entry(which).or_default().mapping = mapping;
+    }
+
+    /// Applies a raw joystick button by index, remapping it onto the canonical
+    /// [`ControllerButton`] through the controller's mapping.
+    ///
+    /// Inputs with no binding in the mapping are ignored.
+    pub fn process_raw_button(&mut self, which: u32, index: u32, pressed: bool) {
+        if let Some(state) = self.controllers.get_mut(&which) {
+            if let Some(&button) = state.mapping.buttons.get(&index) {
+                if pressed {
+                    state.buttons.insert(button);
+                } else {
+                    state.buttons.remove(&button);
+                }
+            }
+        }
+    }
+
+    /// Applies a raw joystick axis by index, remapping it onto the canonical [`ControllerAxis`]
+    /// through the controller's mapping.
+    ///
+    /// Inputs with no binding in the mapping are ignored.
+    pub fn process_raw_axis(&mut self, which: u32, index: u32, value: f32) {
+        if let Some(state) = self.controllers.get_mut(&which) {
+            if let Some(&axis) = state.mapping.axes.get(&index) {
+                state.axes.insert(axis, value);
+            }
+        }
+    }
+
+    /// Applies a raw joystick hat (D-pad) by index, remapping each direction bit set in `value`
+    /// onto the canonical D-pad [`ControllerButton`]s through the controller's mapping.
+    pub fn process_raw_hat(&mut self, which: u32, hat: u32, value: u32) {
+        if let Some(state) = self.controllers.get_mut(&which) {
+            let bindings: Vec<((u32, u32), ControllerButton)> = state
+                .mapping
+                .hats
+                .iter()
+                .filter(|((h, _), _)| *h == hat)
+                .map(|(key, button)| (*key, *button))
+                .collect();
+            for ((_, mask), button) in bindings {
+                if value & mask != 0 {
+                    state.buttons.insert(button);
+                } else {
+                    state.buttons.remove(&button);
+                }
+            }
+        }
+    }
+
+    /// Returns whether `button` is currently held on the controller with instance id `which`.
+    ///
+    /// Returns `false` for controllers that are not connected.
+    #[must_use]
+    pub fn is_pressed(&self, which: u32, button: ControllerButton) -> bool {
+        self.controllers
+            .get(&which)
+            .is_some_and(|state| state.buttons.contains(&button))
+    }
+
+    /// Returns the last reported value of `axis` on the controller with instance id `which`.
+    ///
+    /// Returns `0.0` for unknown controllers or axes that have not moved yet.
+    #[must_use]
+    pub fn axis_value(&self, which: u32, axis: ControllerAxis) -> f32 {
+        self.controllers
+            .get(&which)
+            .and_then(|state| state.axes.get(&axis).copied())
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the instance ids of every currently-connected controller.
+    pub fn connected(&self) -> impl Iterator<Item = u32> + '_ {
+        self.controllers.keys().copied()
+    }
+
+    /// Resolves the mapping for a controller identified by its SDL `guid`, falling back to the
+    /// registered default mapping if the GUID is not in the database.
+    #[must_use]
+    pub fn mapping(&self, guid: &str) -> Option<&ControllerMapping> {
+        self.mappings.get(guid)
+    }
+
+    /// Loads an SDL `gamecontrollerdb.txt` mapping database from `path`, replacing any previously
+    /// loaded GUID entries while preserving the fallback registered with
+    /// [`register_default_mapping`](Self::register_default_mapping).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be read.
+    pub fn load_mappings<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let contents = fs::read_to_string(path)?;
+        let default = self.mappings.default.take();
+        self.mappings = MappingDb::parse(&contents);
+        self.mappings.default = default;
+        Ok(())
+    }
+
+    /// Registers a fallback mapping applied to any controller whose GUID is absent from the loaded
+    /// database.
+    pub fn register_default_mapping(&mut self, mapping: ControllerMapping) {
+        self.mappings.default = Some(mapping);
+    }
+}
+
+/// A GUID-keyed collection of [`ControllerMapping`]s parsed from an SDL
+/// `gamecontrollerdb.txt`-format file, with an optional fallback default.
+#[derive(Debug, Default)]
+pub struct MappingDb {
+    by_guid: HashMap<String, ControllerMapping>,
+    default: Option<ControllerMapping>,
+}
+
+impl MappingDb {
+    /// Parses the contents of a `gamecontrollerdb.txt` file, skipping blank lines, comments and
+    /// entries that do not parse.
+    #[must_use]
+    pub fn parse(contents: &str) -> Self {
+        let mut by_guid = HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match ControllerMapping::parse(line) {
+                Some(mapping) => {
+                    by_guid.insert(mapping.guid.clone(), mapping);
+                }
+                None => warn!("Skipping malformed controller mapping: {}", line),
+            }
+        }
+        MappingDb {
+            by_guid,
+            default: None,
+        }
+    }
+
+    /// Returns the mapping for `guid`, or the registered default mapping if none matches.
+    #[must_use]
+    pub fn get(&self, guid: &str) -> Option<&ControllerMapping> {
+        self.by_guid.get(guid).or(self.default.as_ref())
+    }
+}
+
+/// A single entry from an SDL controller mapping database: a GUID, a human-readable name and the
+/// set of raw joystick bindings assigned to each canonical button and axis.
+#[derive(Debug, Clone)]
+pub struct ControllerMapping {
+    /// The device GUID this mapping applies to.
+    pub guid: String,
+    /// The human-readable controller name.
+    pub name: String,
+    /// Canonical buttons mapped to their raw SDL binding token (e.g. `b0`, `h0.1`).
+    buttons: HashMap<ControllerButton, String>,
+    /// Canonical axes mapped to their raw SDL binding token (e.g. `a1`, `a4~`).
+    axes: HashMap<ControllerAxis, String>,
+}
+
+impl ControllerMapping {
+    /// Parses a single `gamecontrollerdb.txt` line of the form
+    /// `GUID,name,button:binding,axis:binding,...`.
+    ///
+    /// Returns `None` if the line lacks a GUID and name, or if any later field lacks the `key:`
+    /// separator. Unknown `key:value` fields (such as the trailing `platform:` hint) are ignored
+    /// so newer database revisions still load.
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split(',').map(str::trim).filter(|f| !f.is_empty());
+        let guid = fields.next()?.to_string();
+        let name = fields.next()?.to_string();
+
+        let mut buttons = HashMap::new();
+        let mut axes = HashMap::new();
+        for field in fields {
+            let (key, binding) = field.split_once(':')?;
+            if let Some(button) = button_from_sdl(key) {
+                buttons.insert(button, binding.to_string());
+            } else if let Some(axis) = axis_from_sdl(key) {
+                axes.insert(axis, binding.to_string());
+            }
+        }
+
+        Some(ControllerMapping {
+            guid,
+            name,
+            buttons,
+            axes,
+        })
+    }
+
+    /// The raw SDL binding assigned to `button`, if this mapping defines one.
+    #[must_use]
+    pub fn button_binding(&self, button: ControllerButton) -> Option<&str> {
+        self.buttons.get(&button).map(String::as_str)
+    }
+
+    /// The raw SDL binding assigned to `axis`, if this mapping defines one.
+    #[must_use]
+    pub fn axis_binding(&self, axis: ControllerAxis) -> Option<&str> {
+        self.axes.get(&axis).map(String::as_str)
+    }
+
+    /// Resolves the binding tokens into index-keyed lookups so raw joystick inputs can be remapped
+    /// without re-parsing tokens on every event. Bindings the canonical model does not cover
+    /// (e.g. axis-mapped buttons) are dropped.
+    fn resolve(&self) -> ResolvedMapping {
+        let mut resolved = ResolvedMapping::default();
+        for (&button, binding) in &self.buttons {
+            if let Some(index) = parse_button_index(binding) {
+                resolved.buttons.insert(index, button);
+            } else if let Some(hat) = parse_hat(binding) {
+                resolved.hats.insert(hat, button);
+            }
+        }
+        for (&axis, binding) in &self.axes {
+            if let Some(index) = parse_axis_index(binding) {
+                resolved.axes.insert(index, axis);
+            }
+        }
+        resolved
+    }
+}
+
+/// Binding tokens from a [`ControllerMapping`] resolved into index-keyed lookups, in the direction
+/// raw joystick inputs need: joystick index/hat to canonical [`ControllerButton`]/[`ControllerAxis`].
+#[derive(Debug, Default, Clone)]
+struct ResolvedMapping {
+    /// Raw joystick button index to canonical button.
+    buttons: HashMap<u32, ControllerButton>,
+    /// Raw joystick `(hat, direction mask)` to canonical (D-pad) button.
+    hats: HashMap<(u32, u32), ControllerButton>,
+    /// Raw joystick axis index to canonical axis.
+    axes: HashMap<u32, ControllerAxis>,
+}
+
+/// Parses an SDL `bN` button binding into its joystick button index.
+fn parse_button_index(token: &str) -> Option<u32> {
+    token.strip_prefix('b')?.parse().ok()
+}
+
+/// Parses an SDL `aN` axis binding into its joystick axis index, ignoring the optional leading
+/// `+`/`-` half-axis markers and trailing `~` inversion marker.
+fn parse_axis_index(token: &str) -> Option<u32> {
+    token
+        .trim_start_matches(|c| c == '+' || c == '-')
+        .strip_prefix('a')?
+        .trim_end_matches('~')
+        .parse()
+        .ok()
+}
+
+/// Parses an SDL `hHAT.MASK` hat binding into its `(hat, direction mask)` pair.
+fn parse_hat(token: &str) -> Option<(u32, u32)> {
+    let (hat, mask) = token.strip_prefix('h')?.split_once('.')?;
+    Some((hat.parse().ok()?, mask.parse().ok()?))
+}
+
+/// Maps an SDL controller button token to the canonical [`ControllerButton`].
+fn button_from_sdl(token: &str) -> Option<ControllerButton> {
+    Some(match token {
+        "a" => ControllerButton::A,
+        "b" => ControllerButton::B,
+        "x" => ControllerButton::X,
+        "y" => ControllerButton::Y,
+        "dpup" => ControllerButton::DPadUp,
+        "dpdown" => ControllerButton::DPadDown,
+        "dpleft" => ControllerButton::DPadLeft,
+        "dpright" => ControllerButton::DPadRight,
+        "leftshoulder" => ControllerButton::LeftShoulder,
+        "rightshoulder" => ControllerButton::RightShoulder,
+        "leftstick" => ControllerButton::LeftStick,
+        "rightstick" => ControllerButton::RightStick,
+        "back" => ControllerButton::Back,
+        "start" => ControllerButton::Start,
+        "guide" => ControllerButton::Guide,
+        _ => return None,
+    })
+}
+
+/// Maps an SDL controller axis token to the canonical [`ControllerAxis`].
+fn axis_from_sdl(token: &str) -> Option<ControllerAxis> {
+    Some(match token {
+        "leftx" => ControllerAxis::LeftX,
+        "lefty" => ControllerAxis::LeftY,
+        "rightx" => ControllerAxis::RightX,
+        "righty" => ControllerAxis::RightY,
+        "lefttrigger" => ControllerAxis::LeftTrigger,
+        "righttrigger" => ControllerAxis::RightTrigger,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_axis_index, parse_hat, ControllerMapping, MappingDb};
+    use crate::controller::{ControllerAxis, ControllerButton};
+
+    #[test]
+    fn parses_axis_index() {
+        assert_eq!(parse_axis_index("a4"), Some(4));
+        assert_eq!(parse_axis_index("a4~"), Some(4)); // inverted
+        assert_eq!(parse_axis_index("-a2"), Some(2)); // negative half-axis
+        assert_eq!(parse_axis_index("+a2"), Some(2)); // positive half-axis
+        assert_eq!(parse_axis_index("b3"), None); // not an axis
+    }
+
+    #[test]
+    fn parses_hat() {
+        assert_eq!(parse_hat("h0.1"), Some((0, 1)));
+        assert_eq!(parse_hat("h1.4"), Some((1, 4)));
+        assert_eq!(parse_hat("b2"), None);
+        assert_eq!(parse_hat("h0"), None); // missing mask
+    }
+
+    #[test]
+    fn parses_mapping_line() {
+        let mapping =
+            ControllerMapping::parse("030000005e04,X360,a:b0,dpup:h0.1,leftx:a0,platform:Linux")
+                .unwrap();
+        assert_eq!(mapping.guid, "030000005e04");
+        assert_eq!(mapping.name, "X360");
+        assert_eq!(mapping.button_binding(ControllerButton::A), Some("b0"));
+        assert_eq!(mapping.button_binding(ControllerButton::DPadUp), Some("h0.1"));
+        assert_eq!(mapping.axis_binding(ControllerAxis::LeftX), Some("a0"));
+
+        let resolved = mapping.resolve();
+        assert_eq!(resolved.buttons.get(&0), Some(&ControllerButton::A));
+        assert_eq!(resolved.hats.get(&(0, 1)), Some(&ControllerButton::DPadUp));
+        assert_eq!(resolved.axes.get(&0), Some(&ControllerAxis::LeftX));
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let db = MappingDb::parse(
+            "# a comment\n\
+             \n\
+             030000001,Good Pad,a:b0\n\
+             justonefield\n",
+        );
+        assert!(db.get("030000001").is_some());
+        assert!(db.get("justonefield").is_none());
+    }
+}
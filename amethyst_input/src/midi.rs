@@ -0,0 +1,345 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use log::error;
+use midir::{MidiInput, MidiInputConnection, MidiInputPort};
+use serde::{Deserialize, Serialize};
+
+use crate::event::InputEvent;
+
+/// The name the manager uses to open the underlying MIDI client.
+const CLIENT_NAME: &str = "amethyst-midi";
+
+/// MIDI events produced by a [`MidiDeviceManager`], mirroring the SDL controller model.
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum MidiEvent {
+    /// A key was pressed, corresponding to a MIDI `Note On` message.
+    MidiNoteOn {
+        /// The port instance id.
+        which: u32,
+        /// The MIDI channel (0..=15).
+        channel: u8,
+        /// The note number (0..=127), where 60 is middle C.
+        note: u8,
+        /// The strike velocity, normalized to `0.0..=1.0`.
+        velocity: f32,
+    },
+    /// A key was released, corresponding to a MIDI `Note Off` message.
+    MidiNoteOff {
+        /// The port instance id.
+        which: u32,
+        /// The MIDI channel (0..=15).
+        channel: u8,
+        /// The note number (0..=127), where 60 is middle C.
+        note: u8,
+        /// The release velocity, normalized to `0.0..=1.0`.
+        velocity: f32,
+    },
+    /// A continuous controller (knob, pedal, mod wheel, ...) changed value.
+    ///
+    /// Corresponds to a MIDI `Control Change` message.
+    MidiControlChange {
+        /// The port instance id.
+        which: u32,
+        /// The MIDI channel (0..=15).
+        channel: u8,
+        /// The controller number (0..=127).
+        controller: u8,
+        /// The controller value, normalized to `0.0..=1.0`.
+        value: f32,
+    },
+    /// A new MIDI port was discovered. Emitted once per port, even if opening it failed.
+    MidiConnected {
+        /// The port instance id assigned to the newly connected port.
+        which: u32,
+    },
+    /// A MIDI port disappeared, for instance because the device was unplugged.
+    MidiDisconnected {
+        /// The port instance id that was connected to the now-missing port.
+        which: u32,
+    },
+}
+
+impl From<&MidiEvent> for InputEvent {
+    fn from(m: &MidiEvent) -> Self {
+        use self::MidiEvent::{
+            MidiConnected, MidiControlChange, MidiDisconnected, MidiNoteOff, MidiNoteOn,
+        };
+        match *m {
+            MidiNoteOn {
+                which,
+                channel,
+                note,
+                velocity,
+            } => InputEvent::MidiNoteOn {
+                which,
+                channel,
+                note,
+                velocity,
+            },
+            MidiNoteOff {
+                which,
+                channel,
+                note,
+                velocity,
+            } => InputEvent::MidiNoteOff {
+                which,
+                channel,
+                note,
+                velocity,
+            },
+            MidiControlChange {
+                which,
+                channel,
+                controller,
+                value,
+            } => InputEvent::MidiControlChange {
+                which,
+                channel,
+                controller,
+                value,
+            },
+            MidiConnected { which } => InputEvent::MidiConnected { which },
+            MidiDisconnected { which } => InputEvent::MidiDisconnected { which },
+        }
+    }
+}
+
+/// Tracks the set of connected MIDI ports and decodes their messages into [`MidiEvent`]s.
+#[allow(missing_debug_implementations)]
+pub struct MidiDeviceManager {
+    /// The `which` id handed to the next newly discovered port.
+    next_which: u32,
+    /// Maps a port's OS identifier to the stable `which` id assigned to it.
+    ports: HashMap<String, u32>,
+    /// Open input connections, kept alive so their callbacks keep firing, keyed by `which`.
+    connections: HashMap<u32, MidiInputConnection<u32>>,
+    /// Sender handed to each connection callback; decoded events arrive on `receiver`.
+    sender: Sender<MidiEvent>,
+    /// Receiving end drained by [`MidiDeviceManager::poll`].
+    receiver: Receiver<MidiEvent>,
+}
+
+impl MidiDeviceManager {
+    /// Creates an empty manager with no ports opened yet.
+    #[must_use]
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        MidiDeviceManager {
+            next_which: 0,
+            ports: HashMap::new(),
+            connections: HashMap::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Re-enumerates the MIDI input ports, opening new ones and emitting connect/disconnect events
+    /// for ports that appeared or vanished since the last call.
+    pub fn refresh(&mut self) {
+        let input = match MidiInput::new(CLIENT_NAME) {
+            Ok(input) => input,
+            Err(err) => {
+                error!("Failed to initialize MIDI input client: {:?}", err);
+                return;
+            }
+        };
+
+        let mut seen = HashMap::new();
+        for port in input.ports() {
+            let id = port.id();
+            // A port is newly connected the first time we see its id, regardless of whether the
+            // subsequent open succeeds; tracking it by presence in `ports` (not `connections`)
+            // means a port that fails to open is not retried and `MidiConnected` fires only once.
+            let newly_connected = !self.ports.contains_key(&id);
+            let which = *self
+                .ports
+                .entry(id.clone())
+                .or_insert_with(|| allocate_which(&mut self.next_which));
+            seen.insert(id, which);
+
+            if newly_connected {
+                self.open_port(which, &port);
+                let _ = self.sender.send(MidiEvent::MidiConnected { which });
+            }
+        }
+
+        // Anything we were tracking but did not see this round has been unplugged.
+        let removed: Vec<String> = self
+            .ports
+            .keys()
+            .filter(|id| !seen.contains_key(*id))
+            .cloned()
+            .collect();
+        for id in removed {
+            if let Some(which) = self.ports.remove(&id) {
+                self.connections.remove(&which);
+                let _ = self.sender.send(MidiEvent::MidiDisconnected { which });
+            }
+        }
+    }
+
+    /// Drains the events decoded since the last call.
+    pub fn poll(&self) -> impl Iterator<Item = MidiEvent> + '_ {
+        self.receiver.try_iter()
+    }
+
+    /// Opens `port` and wires its callback to forward decoded events tagged with `which`.
+    fn open_port(&mut self, which: u32, port: &MidiInputPort) {
+        let input = match MidiInput::new(CLIENT_NAME) {
+            Ok(input) => input,
+            Err(err) => {
+                error!("Failed to initialize MIDI input client: {:?}", err);
+                return;
+            }
+        };
+
+        let sender = self.sender.clone();
+        let connection = input.connect(
+            port,
+            CLIENT_NAME,
+            move |_timestamp, message, which| {
+                if let Some(event) = decode_message(*which, message) {
+                    let _ = sender.send(event);
+                }
+            },
+            which,
+        );
+
+        match connection {
+            Ok(connection) => {
+                self.connections.insert(which, connection);
+            }
+            Err(err) => error!("Failed to open MIDI port {}: {:?}", which, err),
+        }
+    }
+}
+
+impl Default for MidiDeviceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the current `which` value and advances the counter.
+fn allocate_which(next: &mut u32) -> u32 {
+    let which = *next;
+    *next += 1;
+    which
+}
+
+/// Decodes a raw MIDI message into a [`MidiEvent`], normalizing data bytes to `0.0..=1.0`.
+///
+/// Returns `None` for messages this subsystem does not model (system messages, aftertouch, ...)
+/// and for truncated messages. A `Note On` with zero velocity, which by convention is an alias
+/// for `Note Off`, is decoded as [`MidiEvent::MidiNoteOff`].
+fn decode_message(which: u32, message: &[u8]) -> Option<MidiEvent> {
+    let (&status, data) = message.split_first()?;
+    let channel = status & 0x0F;
+    match status & 0xF0 {
+        0x90 if data.len() >= 2 => {
+            let note = data[0];
+            let velocity = data[1];
+            if velocity == 0 {
+                Some(MidiEvent::MidiNoteOff {
+                    which,
+                    channel,
+                    note,
+                    velocity: 0.0,
+                })
+            } else {
+                Some(MidiEvent::MidiNoteOn {
+                    which,
+                    channel,
+                    note,
+                    velocity: normalize(velocity),
+                })
+            }
+        }
+        0x80 if data.len() >= 2 => Some(MidiEvent::MidiNoteOff {
+            which,
+            channel,
+            note: data[0],
+            velocity: normalize(data[1]),
+        }),
+        0xB0 if data.len() >= 2 => Some(MidiEvent::MidiControlChange {
+            which,
+            channel,
+            controller: data[0],
+            value: normalize(data[1]),
+        }),
+        _ => None,
+    }
+}
+
+/// Normalizes a 7-bit MIDI data byte (0..=127) to `0.0..=1.0`.
+fn normalize(value: u8) -> f32 {
+    f32::from(value) / 127.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_message, MidiEvent};
+
+    #[test]
+    fn decodes_note_on() {
+        assert_eq!(
+            decode_message(3, &[0x92, 60, 127]),
+            Some(MidiEvent::MidiNoteOn {
+                which: 3,
+                channel: 2,
+                note: 60,
+                velocity: 1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn note_on_zero_velocity_is_note_off() {
+        assert_eq!(
+            decode_message(0, &[0x90, 64, 0]),
+            Some(MidiEvent::MidiNoteOff {
+                which: 0,
+                channel: 0,
+                note: 64,
+                velocity: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_note_off() {
+        assert_eq!(
+            decode_message(1, &[0x8F, 64, 64]),
+            Some(MidiEvent::MidiNoteOff {
+                which: 1,
+                channel: 15,
+                note: 64,
+                velocity: 64.0 / 127.0,
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_control_change() {
+        assert_eq!(
+            decode_message(2, &[0xB1, 7, 127]),
+            Some(MidiEvent::MidiControlChange {
+                which: 2,
+                channel: 1,
+                controller: 7,
+                value: 1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_unmodeled_and_short_messages() {
+        assert_eq!(decode_message(0, &[]), None);
+        assert_eq!(decode_message(0, &[0x90, 60]), None); // truncated note on
+        assert_eq!(decode_message(0, &[0xF8]), None); // timing clock, unmodeled
+        assert_eq!(decode_message(0, &[0xD0, 100]), None); // channel aftertouch, unmodeled
+    }
+}